@@ -1,17 +1,139 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
 use std::io;
 use std::cmp;
 
-fn main() {
-    let mut secret_number = [0; 4];
-    for index in 0..4 {
-        secret_number[index] = rand::thread_rng().gen_range(0..10);
+const MAX_ATTEMPTS: u32 = 10;
+
+/// Number of digit positions in the secret.
+const LEN: usize = 4;
+/// Size of the digit alphabet (digits are drawn from `0..BASE`).
+const BASE: u32 = 10;
+
+// Unique mode draws LEN distinct digits out of BASE, so there must be at
+// least as many digits in the alphabet as there are positions to fill.
+const _: () = assert!(LEN <= BASE as usize);
+// BASE doubles as the radix passed to `char::to_digit`, which only supports
+// radixes up to 36.
+const _: () = assert!(BASE <= 36);
+
+enum GameResult {
+    Won,
+    Lost,
+}
+
+/// Whether the secret's digits may repeat.
+///
+/// `snc`/`gnc` tally each digit's occurrences independently of position, so
+/// the min-overlap (ball) computation stays correct under both modes: a
+/// `Unique` secret simply never pushes any tally above 1.
+enum SecretMode {
+    Repeating,
+    Unique,
+}
+
+fn generate_secret(mode: &SecretMode) -> [u32; LEN] {
+    let mut secret = [0; LEN];
+
+    match mode {
+        SecretMode::Repeating => {
+            for index in 0..LEN {
+                secret[index] = rand::thread_rng().gen_range(0..BASE);
+            }
+        }
+        SecretMode::Unique => {
+            let mut pool: Vec<u32> = (0..BASE).collect();
+            pool.shuffle(&mut rand::thread_rng());
+            secret.copy_from_slice(&pool[0..LEN]);
+        }
+    }
+
+    secret
+}
+
+fn has_duplicates(digits: &[u32; LEN]) -> bool {
+    for i in 0..LEN {
+        for j in (i + 1)..LEN {
+            if digits[i] == digits[j] {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// A validated `LEN`-digit guess.
+///
+/// Mirrors the book's `Guess::new` pattern of pushing validation into the
+/// type's constructor, but returns a `Result` instead of panicking so the
+/// main loop can re-prompt on bad input.
+struct Guess {
+    digits: [u32; LEN],
+}
+
+fn format_digits(digits: &[u32; LEN]) -> String {
+    digits
+        .iter()
+        .map(|&digit| char::from_digit(digit, BASE).expect("digit is within 0..BASE"))
+        .collect()
+}
+
+fn print_history(history: &[([u32; LEN], u32, u32)]) {
+    println!("{:>5} | {:<8} | {:>2} | {:>2}", "Guess", "Digits", "A", "B");
+    for (index, (digits, a, b)) in history.iter().enumerate() {
+        println!(
+            "{:>5} | {:<8} | {:>2} | {:>2}",
+            index + 1,
+            format_digits(digits),
+            a,
+            b
+        );
+    }
+}
+
+#[derive(Debug)]
+enum GuessError {
+    WrongLength,
+    NotADigit,
+}
+
+impl Guess {
+    fn parse(input: &str) -> Result<Guess, GuessError> {
+        if input.len() != LEN {
+            return Err(GuessError::WrongLength);
+        }
+
+        let mut digits = [0; LEN];
+        for (index, ch) in input.chars().enumerate() {
+            digits[index] = ch.to_digit(BASE).ok_or(GuessError::NotADigit)?;
+        }
+
+        Ok(Guess { digits })
     }
+}
+
+fn main() {
+    println!("Allow repeated digits in the secret? (y/n)");
+
+    let mut mode_input = String::new();
+    io::stdin()
+        .read_line(&mut mode_input)
+        .expect("Failed to read line");
+
+    let secret_mode = match mode_input.trim().to_lowercase().as_str() {
+        "n" | "no" => SecretMode::Unique,
+        _ => SecretMode::Repeating,
+    };
+
+    let secret_number = generate_secret(&secret_mode);
 
     let mut input_count = 0;
+    let mut history: Vec<([u32; LEN], u32, u32)> = Vec::new();
+
+    let result;
 
     loop {
-        println!("Please input your guess.");
+        println!("Please input your {}-digit guess.", LEN);
 
         let mut guess = String::new();
 
@@ -21,50 +143,92 @@ fn main() {
 
         let guess = guess.trim();
 
-        match guess.trim().parse::<u32>() {
-            Ok(num) => num,
-            Err(_) => continue,
+        if guess == "quit" || guess == "exit" {
+            println!("Goodbye! The secret was {}", format_digits(&secret_number));
+            return;
+        }
+
+        let guess_number = match Guess::parse(guess) {
+            Ok(guess) => guess.digits,
+            Err(GuessError::WrongLength) => {
+                println!("Please type a {}-digit number!", LEN);
+                continue;
+            }
+            Err(GuessError::NotADigit) => {
+                println!("Please use only digits 0-{}!", BASE - 1);
+                continue;
+            }
         };
 
-        if guess.len() != 4 {
+        if matches!(secret_mode, SecretMode::Unique) && has_duplicates(&guess_number) {
+            println!("Digits must be unique in this mode!");
             continue;
-        };
+        }
 
         input_count += 1;
 
-        let mut guess_chars = guess.chars();
-
-        let mut guess_number = [0; 4];
-        for index in 0..4 {
-            guess_number[index] = guess_chars.next().unwrap().to_digit(10).unwrap();
-        }
-
-        let mut snc = [0; 10];
-        let mut gnc = [0; 10];
+        let mut snc = [0; BASE as usize];
+        let mut gnc = [0; BASE as usize];
 
-        for index in 0..4 {
-            snc[secret_number[index]] += 1; 
+        for index in 0..LEN {
+            snc[secret_number[index] as usize] += 1;
             gnc[guess_number[index] as usize] += 1;
         }
 
         let mut b = 0;
-        for index in 0..10 {
-            b += cmp::min(snc[index],gnc[index]);
+        for index in 0..BASE as usize {
+            b += cmp::min(snc[index], gnc[index]);
         }
 
         let mut a = 0;
-        for index in 0..4 {
-            if secret_number[index] as u32 == guess_number[index] {
+        for index in 0..LEN {
+            if secret_number[index] == guess_number[index] {
                 a += 1;
                 b -= 1;
-            } 
+            }
         }
 
         println!("A : {}, B : {}", a, b);
 
-        if a == 4 {
-            println!("You Win! You tried : {}", input_count);
+        history.push((guess_number, a, b));
+        print_history(&history);
+
+        if a == LEN as u32 {
+            result = GameResult::Won;
+            break;
+        }
+
+        if input_count >= MAX_ATTEMPTS {
+            result = GameResult::Lost;
             break;
         }
     }
+
+    match result {
+        GameResult::Won => println!("You Win! You tried: {}", input_count),
+        GameResult::Lost => println!(
+            "You lost — the secret was {}",
+            format_digits(&secret_number)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_wrong_length() {
+        assert!(matches!(Guess::parse("123"), Err(GuessError::WrongLength)));
+    }
+
+    #[test]
+    fn parse_rejects_non_digit() {
+        assert!(matches!(Guess::parse("12a3"), Err(GuessError::NotADigit)));
+    }
+
+    #[test]
+    fn parse_accepts_valid_digits() {
+        assert_eq!(Guess::parse("1234").unwrap().digits, [1, 2, 3, 4]);
+    }
 }